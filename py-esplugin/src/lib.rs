@@ -19,27 +19,80 @@ use esplugin::ParseOptions;
 use esplugin::Plugin;
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::io::Cursor;
+use std::sync::Mutex;
+use std::sync::OnceLock;
+
+fn game_aliases() -> &'static Mutex<HashMap<String, GameId>> {
+    static GAME_ALIASES: OnceLock<Mutex<HashMap<String, GameId>>> = OnceLock::new();
+    GAME_ALIASES.get_or_init(|| {
+        Mutex::new(HashMap::from([
+            ("Morrowind".to_string(), GameId::Morrowind),
+            ("Oblivion".to_string(), GameId::Oblivion),
+            ("Fallout3".to_string(), GameId::Fallout3),
+            ("FalloutNV".to_string(), GameId::FalloutNV),
+            ("Skyrim".to_string(), GameId::Skyrim),
+            ("SkyrimSE".to_string(), GameId::SkyrimSE),
+            ("Fallout4".to_string(), GameId::Fallout4),
+            ("Starfield".to_string(), GameId::Starfield),
+            // LOOT/libloadorder folder aliases.
+            ("Skyrim Special Edition".to_string(), GameId::SkyrimSE),
+            ("fallout4".to_string(), GameId::Fallout4),
+            ("Fallout 3".to_string(), GameId::Fallout3),
+            ("Fallout New Vegas".to_string(), GameId::FalloutNV),
+        ]))
+    })
+}
+
+/// Register an alias for a game already known to the registry (either a
+/// canonical name or a previously-registered alias), so callers can resolve
+/// a `Plugin` by whatever identifier their load-order source uses.
+#[pyfunction]
+fn register_game_alias(alias: &str, game: &str) -> PyResult<()> {
+    let game_id = resolve_game(game)?;
+    game_aliases()
+        .lock()
+        .unwrap()
+        .insert(alias.to_string(), game_id);
+    Ok(())
+}
+
+fn registered_game_names(aliases: &HashMap<String, GameId>) -> Vec<String> {
+    let mut known: Vec<String> = aliases.keys().cloned().collect();
+    known.sort_unstable();
+    known
+}
+
+fn resolve_game(game: &str) -> PyResult<GameId> {
+    let aliases = game_aliases().lock().unwrap();
+    match aliases.get(game) {
+        Some(game_id) => Ok(*game_id),
+        None => Err(PyErr::new::<PyValueError, _>(format!(
+            "Invalid game '{game}', expected one of: {}",
+            registered_game_names(&aliases).join(", ")
+        ))),
+    }
+}
 
 #[pyclass(name = "Plugin")]
 struct PyPlugin {
     plugin: Plugin,
+    header_only: bool,
 }
 
 #[pymethods]
 impl PyPlugin {
     #[new]
     fn new(game: &str, path: &str) -> PyResult<Self> {
-        let game_id = {
-            match game {
-                "Fallout4" => GameId::Fallout4,
-                "SkyrimSE" => GameId::SkyrimSE,
-                "Starfield" => GameId::Starfield,
-                _ => return Err(PyErr::new::<PyValueError, _>("Invalid game")),
-            }
-        };
+        let game_id = resolve_game(game)?;
         let plugin = Plugin::new(game_id, std::path::Path::new(path));
-        Ok(Self { plugin })
+        Ok(Self {
+            plugin,
+            header_only: true,
+        })
     }
 
     fn parse(&mut self, input: &[u8], load_header_only: bool) -> PyResult<()> {
@@ -52,7 +105,10 @@ impl PyPlugin {
                 false => ParseOptions::whole_plugin(),
             },
         ) {
-            Ok(()) => Ok(()),
+            Ok(()) => {
+                self.header_only = load_header_only;
+                Ok(())
+            }
             Err(e) => Err(PyErr::new::<PyValueError, _>(e.to_string())),
         }
     }
@@ -62,7 +118,10 @@ impl PyPlugin {
             true => ParseOptions::header_only(),
             false => ParseOptions::whole_plugin(),
         }) {
-            Ok(()) => Ok(()),
+            Ok(()) => {
+                self.header_only = load_header_only;
+                Ok(())
+            }
             Err(e) => Err(PyErr::new::<PyValueError, _>(e.to_string())),
         }
     }
@@ -99,11 +158,272 @@ impl PyPlugin {
             Err(e) => Err(PyErr::new::<PyValueError, _>(e.to_string())),
         }
     }
+
+    fn masters(&self) -> PyResult<Vec<String>> {
+        match self.plugin.masters() {
+            Ok(masters) => Ok(masters),
+            Err(e) => Err(PyErr::new::<PyValueError, _>(e.to_string())),
+        }
+    }
+
+    fn overlaps_with(&self, other: &PyPlugin) -> PyResult<bool> {
+        if self.header_only || other.header_only {
+            return Err(PyErr::new::<PyValueError, _>(
+                "overlaps_with requires a plugin parsed with load_header_only=false",
+            ));
+        }
+        match self.plugin.overlaps_with(&other.plugin) {
+            Ok(overlaps) => Ok(overlaps),
+            Err(e) => Err(PyErr::new::<PyValueError, _>(e.to_string())),
+        }
+    }
+
+    fn count_override_records(&self) -> PyResult<usize> {
+        if self.header_only {
+            return Err(PyErr::new::<PyValueError, _>(
+                "count_override_records requires a plugin parsed with load_header_only=false",
+            ));
+        }
+        match self.plugin.count_override_records() {
+            Ok(count) => Ok(count),
+            Err(e) => Err(PyErr::new::<PyValueError, _>(e.to_string())),
+        }
+    }
+
+    fn is_master_file(&self) -> bool {
+        self.plugin.is_master_file()
+    }
+
+    fn description(&self) -> PyResult<Option<String>> {
+        match self.plugin.description() {
+            Ok(description) => Ok(description),
+            Err(e) => Err(PyErr::new::<PyValueError, _>(e.to_string())),
+        }
+    }
+
+    fn header_version(&self) -> Option<f32> {
+        self.plugin.header_version()
+    }
+
+    fn record_and_group_count(&self) -> Option<u32> {
+        self.plugin.record_and_group_count()
+    }
+}
+
+/// Resolve a plugin's filename, or fail loudly. A plugin without a
+/// resolvable filename (e.g. one constructed with a path that has no final
+/// file name component, such as an empty string) must never be silently
+/// dropped from a warning scan.
+fn resolve_filename(filename: Option<String>) -> PyResult<String> {
+    filename.ok_or_else(|| {
+        PyErr::new::<PyValueError, _>("plugin has no resolvable filename; its path has no file name")
+    })
+}
+
+fn normalize_filename(filename: &str) -> String {
+    filename.to_lowercase()
+}
+
+/// Case-insensitively filter `masters` down to those not present in `present`.
+fn filter_missing_masters(masters: Vec<String>, present: &HashSet<String>) -> Vec<String> {
+    masters
+        .into_iter()
+        .filter(|master| !present.contains(&normalize_filename(master)))
+        .collect()
+}
+
+/// For each plugin, find the masters it declares that are not present among
+/// the filenames of the given plugins, keyed by the plugin's own filename.
+/// Filenames are compared case-insensitively, since master references are
+/// written verbatim by authoring tools and routinely differ in case from
+/// the referenced file on case-insensitive filesystems.
+#[pyfunction]
+fn find_missing_masters(plugins: Vec<PyRef<PyPlugin>>) -> PyResult<HashMap<String, Vec<String>>> {
+    let mut present = HashSet::with_capacity(plugins.len());
+    for plugin in &plugins {
+        let filename = resolve_filename(plugin.plugin.filename())?;
+        present.insert(normalize_filename(&filename));
+    }
+
+    let mut missing = HashMap::new();
+    for plugin in &plugins {
+        let filename = resolve_filename(plugin.plugin.filename())?;
+        let masters = match plugin.plugin.masters() {
+            Ok(masters) => masters,
+            Err(e) => return Err(PyErr::new::<PyValueError, _>(e.to_string())),
+        };
+        missing.insert(filename, filter_missing_masters(masters, &present));
+    }
+    Ok(missing)
+}
+
+/// Pairwise-scan the given plugins and return the filenames of those pairs
+/// whose record groups overlap, i.e. plugins that edit the same records.
+#[pyfunction]
+fn find_overlapping_pairs(plugins: Vec<PyRef<PyPlugin>>) -> PyResult<Vec<(String, String)>> {
+    let mut overlapping = Vec::new();
+    for (i, plugin) in plugins.iter().enumerate() {
+        for other in &plugins[i + 1..] {
+            if plugin.header_only || other.header_only {
+                return Err(PyErr::new::<PyValueError, _>(
+                    "find_overlapping_pairs requires plugins parsed with load_header_only=false",
+                ));
+            }
+            let overlaps = match plugin.plugin.overlaps_with(&other.plugin) {
+                Ok(overlaps) => overlaps,
+                Err(e) => return Err(PyErr::new::<PyValueError, _>(e.to_string())),
+            };
+            if overlaps {
+                let a = resolve_filename(plugin.plugin.filename())?;
+                let b = resolve_filename(other.plugin.filename())?;
+                overlapping.push((a, b));
+            }
+        }
+    }
+    Ok(overlapping)
+}
+
+/// Parse a whole load order in parallel, releasing the GIL for the duration
+/// of the scan. Each path is read and parsed independently on the rayon
+/// thread pool; a file failing to parse does not abort the others, but is
+/// collected into a single error naming every failing path.
+#[pyfunction]
+fn parse_load_order(
+    py: Python<'_>,
+    game: &str,
+    paths: Vec<String>,
+    load_header_only: bool,
+) -> PyResult<HashMap<String, PyPlugin>> {
+    let game_id = resolve_game(game)?;
+    let options = match load_header_only {
+        true => ParseOptions::header_only(),
+        false => ParseOptions::whole_plugin(),
+    };
+
+    let results: Vec<(String, Result<Plugin, String>)> = py.allow_threads(|| {
+        paths
+            .par_iter()
+            .map(|path| {
+                let mut plugin = Plugin::new(game_id, std::path::Path::new(path));
+                match plugin.parse_file(options) {
+                    Ok(()) => (path.clone(), Ok(plugin)),
+                    Err(e) => (path.clone(), Err(e.to_string())),
+                }
+            })
+            .collect()
+    });
+
+    let mut parsed = HashMap::new();
+    let mut errors = Vec::new();
+    for (path, result) in results {
+        match result {
+            Ok(plugin) => {
+                parsed.insert(
+                    path,
+                    PyPlugin {
+                        plugin,
+                        header_only: load_header_only,
+                    },
+                );
+            }
+            Err(e) => errors.push(format!("{path}: {e}")),
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(PyErr::new::<PyValueError, _>(format!(
+            "failed to parse {} of {} plugins:\n{}",
+            errors.len(),
+            paths.len(),
+            errors.join("\n")
+        )));
+    }
+
+    Ok(parsed)
 }
 
 #[pymodule]
 #[pyo3(name = "esplugin")]
 fn py_esplugin(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyPlugin>()?;
+    m.add_function(wrap_pyfunction!(find_missing_masters, m)?)?;
+    m.add_function(wrap_pyfunction!(find_overlapping_pairs, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_load_order, m)?)?;
+    m.add_function(wrap_pyfunction!(register_game_alias, m)?)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_filename_passes_through_some() {
+        assert_eq!(
+            resolve_filename(Some("Skyrim.esm".to_string())).unwrap(),
+            "Skyrim.esm"
+        );
+    }
+
+    #[test]
+    fn resolve_filename_errors_on_none() {
+        assert!(resolve_filename(None).is_err());
+    }
+
+    #[test]
+    fn filter_missing_masters_ignores_case() {
+        let present: HashSet<String> = ["skyrim.esm".to_string()].into_iter().collect();
+        let masters = vec!["Skyrim.ESM".to_string(), "Missing.esp".to_string()];
+        assert_eq!(
+            filter_missing_masters(masters, &present),
+            vec!["Missing.esp".to_string()]
+        );
+    }
+
+    #[test]
+    fn filter_missing_masters_empty_when_all_present() {
+        let present: HashSet<String> = ["skyrim.esm".to_string()].into_iter().collect();
+        let masters = vec!["Skyrim.esm".to_string()];
+        assert!(filter_missing_masters(masters, &present).is_empty());
+    }
+
+    #[test]
+    fn resolve_game_finds_seeded_canonical_name() {
+        assert!(matches!(resolve_game("Skyrim"), Ok(GameId::Skyrim)));
+    }
+
+    #[test]
+    fn resolve_game_finds_seeded_loot_alias() {
+        assert!(matches!(
+            resolve_game("Skyrim Special Edition"),
+            Ok(GameId::SkyrimSE)
+        ));
+    }
+
+    #[test]
+    fn resolve_game_unknown_alias_lists_registered_names() {
+        assert!(resolve_game("NotAGame").is_err());
+
+        let known = registered_game_names(&game_aliases().lock().unwrap());
+        assert!(known.contains(&"Skyrim".to_string()));
+        assert!(known.contains(&"Starfield".to_string()));
+        // The listed names are sorted, so lookups in the error message are stable.
+        let mut sorted = known.clone();
+        sorted.sort_unstable();
+        assert_eq!(known, sorted);
+    }
+
+    #[test]
+    fn register_game_alias_then_resolve_round_trips() {
+        register_game_alias("__test_register_then_resolve__", "Skyrim").unwrap();
+        assert!(matches!(
+            resolve_game("__test_register_then_resolve__"),
+            Ok(GameId::Skyrim)
+        ));
+    }
+
+    #[test]
+    fn register_game_alias_errors_for_unknown_base_game() {
+        assert!(register_game_alias("__test_unknown_base__", "NotAGame").is_err());
+    }
+}